@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Persisted defaults, loaded from `~/.config/podcast-archiver/config.toml` (platform config
+/// dir via `dirs`). CLI flags always take precedence over these when both are present.
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct Config {
+    /// Feed URLs to archive when the tool is run with no `--url`, `--opml`, `--export-opml`, or subcommand.
+    #[serde(default)]
+    pub feeds: Vec<String>,
+    pub output: Option<String>,
+    pub threads: Option<usize>,
+    pub metadata: Option<bool>,
+    pub count: Option<usize>,
+}
+
+/// Path to the config file: `<platform config dir>/podcast-archiver/config.toml`.
+pub fn config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("podcast-archiver").join("config.toml"))
+}
+
+/// Loads the config file if one exists, falling back to an empty `Config` otherwise (a missing
+/// or unparsable config file is not an error — the tool just has no stored defaults yet).
+pub fn load() -> Config {
+    let Some(path) = config_path() else {
+        return Config::default();
+    };
+
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => toml::from_str(&contents).unwrap_or_default(),
+        Err(_) => Config::default(),
+    }
+}
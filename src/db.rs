@@ -0,0 +1,90 @@
+use rusqlite::{params, Connection, OptionalExtension};
+use std::time::Duration;
+
+/// A feed that has been archived at least once, as recorded in the database.
+pub struct SubscribedFeed {
+    pub url: String,
+    pub title: String,
+    pub output_dir: String,
+}
+
+/// Opens (creating if necessary) the SQLite database that tracks subscribed feeds and the
+/// episodes already downloaded from them, and ensures its schema exists.
+///
+/// Concurrent downloads each open their own connection to record completion, so WAL mode plus a
+/// busy timeout are set here to let those writers queue up instead of failing with
+/// `database is locked`.
+pub fn open(db_path: &str) -> Result<Connection, rusqlite::Error> {
+    let conn = Connection::open(db_path)?;
+    conn.busy_timeout(Duration::from_secs(30))?;
+    conn.pragma_update(None, "journal_mode", "WAL")?;
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS feeds (
+            url TEXT PRIMARY KEY,
+            title TEXT NOT NULL,
+            output_dir TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS downloads (
+            feed_url TEXT NOT NULL,
+            guid TEXT NOT NULL,
+            enclosure_url TEXT NOT NULL,
+            PRIMARY KEY (feed_url, guid)
+        );",
+    )?;
+    Ok(conn)
+}
+
+/// Records (or updates) a feed's title and output directory, keyed by feed URL.
+pub fn upsert_feed(
+    conn: &Connection,
+    url: &str,
+    title: &str,
+    output_dir: &str,
+) -> Result<(), rusqlite::Error> {
+    conn.execute(
+        "INSERT INTO feeds (url, title, output_dir) VALUES (?1, ?2, ?3)
+         ON CONFLICT(url) DO UPDATE SET title = excluded.title, output_dir = excluded.output_dir",
+        params![url, title, output_dir],
+    )?;
+    Ok(())
+}
+
+/// Lists every feed that has been archived at least once, alphabetically by title.
+pub fn list_feeds(conn: &Connection) -> Result<Vec<SubscribedFeed>, rusqlite::Error> {
+    let mut stmt = conn.prepare("SELECT url, title, output_dir FROM feeds ORDER BY title")?;
+    let feeds = stmt
+        .query_map([], |row| {
+            Ok(SubscribedFeed {
+                url: row.get(0)?,
+                title: row.get(1)?,
+                output_dir: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(feeds)
+}
+
+/// Returns whether `guid` has already been downloaded for `feed_url`.
+pub fn is_downloaded(conn: &Connection, feed_url: &str, guid: &str) -> Result<bool, rusqlite::Error> {
+    conn.query_row(
+        "SELECT 1 FROM downloads WHERE feed_url = ?1 AND guid = ?2",
+        params![feed_url, guid],
+        |_| Ok(()),
+    )
+    .optional()
+    .map(|row| row.is_some())
+}
+
+/// Records that `guid` (with its enclosure URL) has been downloaded for `feed_url`.
+pub fn record_download(
+    conn: &Connection,
+    feed_url: &str,
+    guid: &str,
+    enclosure_url: &str,
+) -> Result<(), rusqlite::Error> {
+    conn.execute(
+        "INSERT OR REPLACE INTO downloads (feed_url, guid, enclosure_url) VALUES (?1, ?2, ?3)",
+        params![feed_url, guid, enclosure_url],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,72 @@
+/// Parses an `itunes:duration` value, which may be `HH:MM:SS`, `MM:SS`, or a bare count of
+/// seconds, into a total number of seconds.
+pub fn parse_itunes_duration(raw: &str) -> Option<u64> {
+    let parts: Vec<&str> = raw.trim().split(':').collect();
+    let values: Vec<u64> = parts.iter().filter_map(|p| p.trim().parse::<u64>().ok()).collect();
+    if values.len() != parts.len() || values.is_empty() {
+        return None;
+    }
+
+    let seconds = match values.len() {
+        1 => values[0],
+        2 => values[0] * 60 + values[1],
+        3 => values[0] * 3600 + values[1] * 60 + values[2],
+        _ => return None,
+    };
+    Some(seconds)
+}
+
+/// Extracts an episode's duration in seconds, preferring `itunes:duration` and falling back to
+/// `media:content`'s `duration` attribute (from `item.extensions()`) when the iTunes tag is
+/// absent.
+pub fn episode_duration_seconds(item: &rss::Item) -> Option<u64> {
+    if let Some(duration) = item.itunes_ext().and_then(|ext| ext.duration()) {
+        if let Some(seconds) = parse_itunes_duration(duration) {
+            return Some(seconds);
+        }
+    }
+
+    item.extensions()
+        .values()
+        .flat_map(|namespace| namespace.values())
+        .flatten()
+        .find(|ext| ext.name() == "content")
+        .and_then(|ext| ext.attrs().get("duration"))
+        .and_then(|duration| parse_itunes_duration(duration))
+}
+
+/// Parses a human-friendly duration like `30m`, `1h30m`, `45s`, or a bare number of seconds, as
+/// accepted by `--min-duration`/`--max-duration`.
+pub fn parse_human_duration(raw: &str) -> Result<u64, String> {
+    let raw = raw.trim();
+    if let Ok(seconds) = raw.parse::<u64>() {
+        return Ok(seconds);
+    }
+
+    let mut total = 0u64;
+    let mut number = String::new();
+    for c in raw.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        let value: u64 = number
+            .parse()
+            .map_err(|_| format!("invalid duration: {}", raw))?;
+        number.clear();
+
+        total += match c {
+            'h' | 'H' => value * 3600,
+            'm' | 'M' => value * 60,
+            's' | 'S' => value,
+            _ => return Err(format!("invalid duration: {}", raw)),
+        };
+    }
+
+    if !number.is_empty() {
+        return Err(format!("invalid duration: {}", raw));
+    }
+
+    Ok(total)
+}
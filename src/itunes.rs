@@ -0,0 +1,35 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+struct SearchResponse {
+    results: Vec<SearchResult>,
+}
+
+/// A single hit from the iTunes podcast directory.
+#[derive(Deserialize, Debug, Clone)]
+pub struct SearchResult {
+    #[serde(rename = "trackName")]
+    pub track_name: String,
+    #[serde(rename = "artistName")]
+    pub artist_name: Option<String>,
+    #[serde(rename = "feedUrl")]
+    pub feed_url: Option<String>,
+}
+
+/// Queries the public iTunes Search API for podcasts whose name matches `query`, dropping any
+/// result that has no `feedUrl` to archive.
+pub async fn search_podcasts(query: &str) -> Result<Vec<SearchResult>, Box<dyn std::error::Error>> {
+    let response = reqwest::Client::new()
+        .get("https://itunes.apple.com/search")
+        .query(&[("media", "podcast"), ("term", query)])
+        .send()
+        .await?
+        .json::<SearchResponse>()
+        .await?;
+
+    Ok(response
+        .results
+        .into_iter()
+        .filter(|result| result.feed_url.is_some())
+        .collect())
+}
@@ -1,6 +1,5 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use rss::Channel;
-use reqwest;
 use std::fs::{File, create_dir_all};
 use std::io::Write;
 use std::path::Path;
@@ -10,30 +9,79 @@ use futures::StreamExt;
 use tokio::sync::Semaphore;
 use indicatif::{ProgressBar, ProgressStyle, MultiProgress};
 use serde_json::{to_string_pretty};
-use chrono;
+
+mod config;
+mod db;
+mod duration;
+mod itunes;
+mod opml;
+mod tags;
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 struct Args {
+    #[clap(subcommand)]
+    command: Option<Command>,
+
     /// The URL of the podcast RSS feed
     #[clap(short, long)]
-    url: String,
+    url: Option<String>,
 
-    /// The directory to save the episodes
+    /// An OPML file listing feeds to archive, one subdirectory per podcast
+    #[clap(long)]
+    opml: Option<String>,
+
+    /// Write an OPML file of everything found in the output directory and exit
+    #[clap(long)]
+    export_opml: Option<String>,
+
+    /// The directory to save the episodes (required unless running `update` or `search`, or set in the config file)
     #[clap(short, long)]
-    output: String,
+    output: Option<String>,
 
     /// The number of episodes to download
     #[clap(short, long)]
     count: Option<usize>,
 
     /// Number of concurrent downloads
-    #[clap(short, long, default_value = "1")]
-    threads: usize,
+    #[clap(short, long)]
+    threads: Option<usize>,
 
     /// Save episode metadata as JSON files
     #[clap(short, long)]
     metadata: bool,
+
+    /// Embed episode metadata (title, album, artist, date, description, artwork) as audio tags
+    #[clap(long)]
+    tag: bool,
+
+    /// Path to the SQLite database tracking subscribed feeds and downloaded episodes
+    #[clap(long, default_value = "podcast_archiver.db")]
+    db: String,
+
+    /// Skip episodes shorter than this (e.g. `30m`, `1h30m`, or a bare number of seconds)
+    #[clap(long, value_parser = duration::parse_human_duration)]
+    min_duration: Option<u64>,
+
+    /// Skip episodes longer than this (e.g. `30m`, `1h30m`, or a bare number of seconds)
+    #[clap(long, value_parser = duration::parse_human_duration)]
+    max_duration: Option<u64>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Re-fetch every subscribed feed and download only episodes not already recorded
+    Update,
+
+    /// Search the iTunes podcast directory by name
+    Search {
+        /// Text to search for, e.g. a show name
+        query: String,
+
+        /// Just list matching feed URLs instead of prompting to archive one
+        #[clap(long)]
+        print_only: bool,
+    },
 }
 
 #[derive(serde::Serialize)]
@@ -42,6 +90,7 @@ struct EpisodeMetadata {
     description: Option<String>,
     pub_date: Option<String>,
     duration: Option<String>,
+    duration_seconds: Option<u64>,
     author: Option<String>,
     file_url: String,
     guid: Option<String>,
@@ -59,6 +108,7 @@ fn save_episode_metadata(
         description: item.description().map(|s| s.to_string()),
         pub_date: item.pub_date().map(|s| s.to_string()),
         duration: item.itunes_ext().and_then(|ext| ext.duration()).map(|s| s.to_string()),
+        duration_seconds: duration::episode_duration_seconds(item),
         author: item.author().map(|s| s.to_string()),
         file_url: item.enclosure().map(|e| e.url().to_string()).unwrap_or_default(),
         guid: item.guid().map(|g| g.value().to_string()),
@@ -75,21 +125,54 @@ fn save_episode_metadata(
     Ok(())
 }
 
-async fn download_episode(
+/// What to fetch and where to put it.
+struct EpisodeDownload {
     url: String,
     filename: String,
     output_dir: String,
+    item: rss::Item,
+}
+
+/// Everything about a download that isn't the episode itself: progress-bar bookkeeping,
+/// concurrency control, and the bookkeeping around the download (metadata/tags/db) to perform.
+struct DownloadContext {
     episode_index: usize,
     total_episodes: usize,
     semaphore: Arc<Semaphore>,
     multi_progress: Arc<MultiProgress>,
-    item: rss::Item,
     save_metadata: bool,
+    save_tags: bool,
+    db_path: String,
+    feed_url: String,
+    guid: String,
+    podcast_title: String,
+    channel_image_url: Option<String>,
+}
+
+async fn download_episode(
+    episode: EpisodeDownload,
+    ctx: DownloadContext,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let EpisodeDownload { url, filename, output_dir, item } = episode;
+    let DownloadContext {
+        episode_index,
+        total_episodes,
+        semaphore,
+        multi_progress,
+        save_metadata,
+        save_tags,
+        db_path,
+        feed_url,
+        guid,
+        podcast_title,
+        channel_image_url,
+    } = ctx;
+
     let _permit = semaphore.acquire().await?;
-    
+
     let file_path = format!("{}/{}", output_dir, filename);
-    
+    let part_path = format!("{}.part", file_path);
+
     // Check if file already exists
     if Path::new(&file_path).exists() {
         let pb = multi_progress.add(ProgressBar::new_spinner());
@@ -99,10 +182,10 @@ async fn download_episode(
                 .unwrap()
         );
         pb.set_message(format!("[{}/{}] Skipping (already exists): {}", episode_index + 1, total_episodes, filename));
-        
+
         // Simulate a brief delay to show the skip message
         tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-        
+
         pb.finish_with_message(format!("[{}/{}] Skipped: {}", episode_index + 1, total_episodes, filename));
         return Ok(());
     }
@@ -113,7 +196,7 @@ async fn download_episode(
             eprintln!("Warning: Failed to save metadata for {}: {}", filename, e);
         }
     }
-    
+
     // Create progress bar for this download using the multi-progress
     let pb = multi_progress.add(ProgressBar::new_spinner());
     pb.set_style(
@@ -123,14 +206,36 @@ async fn download_episode(
     );
     pb.set_message(format!("[{}/{}] Downloading: {}", episode_index + 1, total_episodes, filename));
 
-    let response = reqwest::get(&url).await?;
-    
-    // Get content length for progress tracking
-    let total_size = response.content_length().unwrap_or(0);
-    
+    // Resume a partial download, if one is already on disk, by asking the server for
+    // everything past the bytes we already have.
+    let existing_len = std::fs::metadata(&part_path).map(|m| m.len()).unwrap_or(0);
+
+    let client = reqwest::Client::new();
+    let mut request = client.get(&url);
+    if existing_len > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", existing_len));
+    }
+    let response = request.send().await?;
+
+    if response.status() != reqwest::StatusCode::OK && response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+        return Err(format!("server returned {} while downloading {}", response.status(), url).into());
+    }
+
+    let (mut dest, mut downloaded, total_size) = if response.status() == reqwest::StatusCode::PARTIAL_CONTENT {
+        let total = existing_len + response.content_length().unwrap_or(0);
+        let dest = std::fs::OpenOptions::new().append(true).open(&part_path)?;
+        (dest, existing_len, total)
+    } else {
+        // Server doesn't support (or ignored) the range request; restart from scratch.
+        let total = response.content_length().unwrap_or(0);
+        let dest = File::create(&part_path)?;
+        (dest, 0, total)
+    };
+
     if total_size > 0 {
         // Switch to determinate progress bar if we know the size
         pb.set_length(total_size);
+        pb.set_position(downloaded);
         pb.set_style(
             ProgressStyle::default_bar()
                 .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta}) {msg}")
@@ -140,20 +245,61 @@ async fn download_episode(
     }
 
     let mut response = response;
-    let mut dest = File::create(&file_path)?;
-    let mut downloaded: u64 = 0;
-    
+
     while let Some(chunk) = response.chunk().await? {
         dest.write_all(&chunk)?;
         downloaded += chunk.len() as u64;
-        
+
         if total_size > 0 {
             pb.set_position(downloaded);
         }
     }
-    
+    drop(dest);
+
+    if let Some(expected_size) = expected_enclosure_size(&item) {
+        let actual_size = std::fs::metadata(&part_path)?.len();
+        if actual_size != expected_size {
+            pb.finish_with_message(format!(
+                "[{}/{}] Size mismatch, refusing to finalize: {}",
+                episode_index + 1, total_episodes, filename
+            ));
+            eprintln!(
+                "Warning: {} is {} bytes, expected {} from the enclosure; leaving {} in place for a future resume",
+                filename, actual_size, expected_size, part_path
+            );
+            return Ok(());
+        }
+    }
+
+    if let Some(expected_md5) = expected_enclosure_md5(&item) {
+        let actual_md5 = format!("{:x}", md5::compute(std::fs::read(&part_path)?));
+        if !actual_md5.eq_ignore_ascii_case(&expected_md5) {
+            pb.finish_with_message(format!(
+                "[{}/{}] Checksum mismatch, refusing to finalize: {}",
+                episode_index + 1, total_episodes, filename
+            ));
+            eprintln!("Warning: {} failed MD5 verification (expected {}, got {})", filename, expected_md5, actual_md5);
+            return Ok(());
+        }
+    }
+
+    std::fs::rename(&part_path, &file_path)?;
+
     pb.finish_with_message(format!("[{}/{}] Finished: {}", episode_index + 1, total_episodes, filename));
 
+    if save_tags {
+        let episode_tags = tags::build_episode_tags(&item, &podcast_title, channel_image_url.as_deref());
+        if let Err(e) = tags::write_tags(&file_path, &episode_tags).await {
+            eprintln!("Warning: Failed to write tags for {}: {}", filename, e);
+        }
+    }
+
+    if let Ok(conn) = db::open(&db_path) {
+        if let Err(e) = db::record_download(&conn, &feed_url, &guid, &url) {
+            eprintln!("Warning: Failed to record download for {} in database: {}", filename, e);
+        }
+    }
+
     Ok(())
 }
 
@@ -176,26 +322,124 @@ fn sanitize_filename(title: &str) -> String {
         .to_string()
 }
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args = Args::parse();
+/// Builds the enclosure URL and on-disk filename for an episode, or `None` if it has no
+/// enclosure to download.
+fn build_episode_filename(item: &rss::Item) -> Option<(String, String)> {
+    let enclosure = item.enclosure()?;
+    let url = enclosure.url().to_string();
 
-    let content = reqwest::get(args.url)
+    // Get the original file extension from the URL
+    let original_extension = Path::new(&url)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("mp3"); // Default to mp3 if no extension found
+
+    // Get the episode title and sanitize it for use as filename
+    let episode_title = item.title().unwrap_or("Unknown Episode");
+    let sanitized_title = sanitize_filename(episode_title);
+
+    // Get publication date for filename prefix
+    let date_prefix = if let Some(pub_date) = item.pub_date() {
+        // Parse the date and format as YYYY-MM-DD
+        if let Ok(parsed_date) = chrono::DateTime::parse_from_rfc2822(pub_date) {
+            parsed_date.format("%Y-%m-%d - ").to_string()
+        } else if let Ok(parsed_date) = chrono::NaiveDateTime::parse_from_str(pub_date, "%a, %d %b %Y %H:%M:%S %z") {
+            parsed_date.format("%Y-%m-%d - ").to_string()
+        } else {
+            "".to_string() // Fallback for unparseable dates
+        }
+    } else {
+        "".to_string() // Fallback for missing dates
+    };
+
+    // Create filename with date prefix, episode title and original extension
+    let filename = format!("{}{}.{}", date_prefix, sanitized_title, original_extension);
+    Some((url, filename))
+}
+
+/// The enclosure's advertised size in bytes, when the feed provides one. A parsed length of `0`
+/// means the feed doesn't actually know the size (common for enclosures with `length="0"`), so
+/// that's treated the same as no length being present.
+fn expected_enclosure_size(item: &rss::Item) -> Option<u64> {
+    item.enclosure()
+        .and_then(|e| e.length().parse::<u64>().ok())
+        .filter(|&length| length > 0)
+}
+
+/// Looks for an MD5 checksum on the enclosure via any `hash` extension element (e.g. Media
+/// RSS's `media:hash algo="md5"`), since the `rss` crate's `Enclosure` type has no field for it.
+fn expected_enclosure_md5(item: &rss::Item) -> Option<String> {
+    item.extensions()
+        .values()
+        .flat_map(|namespace| namespace.values())
+        .flatten()
+        .find(|ext| {
+            ext.name() == "hash"
+                && ext
+                    .attrs()
+                    .get("algo")
+                    .map(|algo| algo.eq_ignore_ascii_case("md5"))
+                    .unwrap_or(true)
+        })
+        .and_then(|ext| ext.value().map(|v| v.to_string()))
+}
+
+/// Whether an episode's duration falls within `--min-duration`/`--max-duration`. Episodes whose
+/// duration can't be determined are never filtered out.
+fn duration_in_range(item: &rss::Item, args: &Args) -> bool {
+    if args.min_duration.is_none() && args.max_duration.is_none() {
+        return true;
+    }
+
+    match duration::episode_duration_seconds(item) {
+        Some(seconds) => {
+            args.min_duration.is_none_or(|min| seconds >= min)
+                && args.max_duration.is_none_or(|max| seconds <= max)
+        }
+        None => true,
+    }
+}
+
+/// The identifier used to track whether an episode has already been downloaded: the item's
+/// GUID when present, falling back to its enclosure URL.
+fn episode_guid(item: &rss::Item) -> String {
+    item.guid()
+        .map(|g| g.value().to_string())
+        .or_else(|| item.enclosure().map(|e| e.url().to_string()))
+        .unwrap_or_default()
+}
+
+/// Downloads every (selected) episode of a single feed into its own `output/<podcast_title>/`
+/// directory. Shared by the plain `--url` path and by each entry of an `--opml` import.
+async fn archive_feed(feed_url: &str, args: &Args) -> Result<(), Box<dyn std::error::Error>> {
+    let output = args
+        .output
+        .as_deref()
+        .ok_or("--output is required when archiving a feed")?;
+
+    let content = reqwest::get(feed_url)
         .await?
         .bytes()
         .await?;
 
     let channel = Channel::read_from(&content[..])?;
     let podcast_title = channel.title();
-    let podcast_dir = format!("{}/{}", &args.output, podcast_title);
+    let podcast_dir = format!("{}/{}", output, podcast_title);
 
     // Create output directory if it doesn't exist
     create_dir_all(&podcast_dir)?;
+    opml::write_feed_marker(&podcast_dir, feed_url)?;
+
+    {
+        let conn = db::open(&args.db)?;
+        db::upsert_feed(&conn, feed_url, podcast_title, &podcast_dir)?;
+    }
 
     println!("[{}] Found {} episodes", podcast_title, channel.items().len());
 
+    let channel_image_url = channel.image().map(|image| image.url().to_string());
     let max_episodes = args.count.unwrap_or(channel.items().len());
-    let semaphore = Arc::new(Semaphore::new(args.threads));
+    let semaphore = Arc::new(Semaphore::new(args.threads.unwrap_or(1)));
     let multi_progress = Arc::new(MultiProgress::new());
     let mut downloads = FuturesUnordered::new();
     let mut downloaded_episodes = 0;
@@ -205,52 +449,36 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             break;
         }
 
-        if let Some(enclosure) = item.enclosure() {
-            let url = enclosure.url().to_string();
-            
-            // Get the original file extension from the URL
-            let original_extension = Path::new(&url)
-                .extension()
-                .and_then(|ext| ext.to_str())
-                .unwrap_or("mp3"); // Default to mp3 if no extension found
-            
-            // Get the episode title and sanitize it for use as filename
-            let episode_title = item.title().unwrap_or("Unknown Episode");
-            let sanitized_title = sanitize_filename(episode_title);
-            
-            // Get publication date for filename prefix
-            let date_prefix = if let Some(pub_date) = item.pub_date() {
-                // Parse the date and format as YYYY-MM-DD
-                if let Ok(parsed_date) = chrono::DateTime::parse_from_rfc2822(pub_date) {
-                    parsed_date.format("%Y-%m-%d - ").to_string()
-                } else if let Ok(parsed_date) = chrono::NaiveDateTime::parse_from_str(pub_date, "%a, %d %b %Y %H:%M:%S %z") {
-                    parsed_date.format("%Y-%m-%d - ").to_string()
-                } else {
-                    "".to_string() // Fallback for unparseable dates
-                }
-            } else {
-                "".to_string() // Fallback for missing dates
-            };
-            
-            // Create filename with date prefix, episode title and original extension
-            let filename = format!("{}{}.{}", date_prefix, sanitized_title, original_extension);
-
-            let output_dir = podcast_dir.clone();
-            let semaphore_clone = semaphore.clone();
-            let multi_progress_clone = multi_progress.clone();
-            
+        if !duration_in_range(item, args) {
+            println!("[{}/{}] Skipping (outside --min-duration/--max-duration): {}", index + 1, max_episodes, item.title().unwrap_or("Unknown title"));
+            continue;
+        }
+
+        if let Some((url, filename)) = build_episode_filename(item) {
+            let guid = episode_guid(item);
+
             let download_future = download_episode(
-                url,
-                filename,
-                output_dir,
-                index,
-                max_episodes,
-                semaphore_clone,
-                multi_progress_clone,
-                item.clone(),
-                args.metadata,
+                EpisodeDownload {
+                    url,
+                    filename,
+                    output_dir: podcast_dir.clone(),
+                    item: item.clone(),
+                },
+                DownloadContext {
+                    episode_index: index,
+                    total_episodes: max_episodes,
+                    semaphore: semaphore.clone(),
+                    multi_progress: multi_progress.clone(),
+                    save_metadata: args.metadata,
+                    save_tags: args.tag,
+                    db_path: args.db.clone(),
+                    feed_url: feed_url.to_string(),
+                    guid,
+                    podcast_title: podcast_title.to_string(),
+                    channel_image_url: channel_image_url.clone(),
+                },
             );
-            
+
             downloads.push(download_future);
             downloaded_episodes += 1;
         } else {
@@ -267,3 +495,208 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+/// Re-fetches every feed recorded in the database and downloads only episodes whose GUID
+/// isn't already present, instead of re-scanning the filesystem.
+async fn update_feed(feed: &db::SubscribedFeed, args: &Args) -> Result<(), Box<dyn std::error::Error>> {
+    let content = reqwest::get(&feed.url).await?.bytes().await?;
+    let channel = Channel::read_from(&content[..])?;
+
+    let pending: Vec<&rss::Item> = {
+        let conn = db::open(&args.db)?;
+        channel
+            .items()
+            .iter()
+            .filter(|item| !db::is_downloaded(&conn, &feed.url, &episode_guid(item)).unwrap_or(false))
+            .filter(|item| duration_in_range(item, args))
+            .collect()
+    };
+
+    println!("[{}] {} new episode(s)", feed.title, pending.len());
+
+    let channel_image_url = channel.image().map(|image| image.url().to_string());
+    let semaphore = Arc::new(Semaphore::new(args.threads.unwrap_or(1)));
+    let multi_progress = Arc::new(MultiProgress::new());
+    let mut downloads = FuturesUnordered::new();
+    let total = pending.len();
+
+    for (index, item) in pending.into_iter().enumerate() {
+        if let Some((url, filename)) = build_episode_filename(item) {
+            let guid = episode_guid(item);
+            let download_future = download_episode(
+                EpisodeDownload {
+                    url,
+                    filename,
+                    output_dir: feed.output_dir.clone(),
+                    item: item.clone(),
+                },
+                DownloadContext {
+                    episode_index: index,
+                    total_episodes: total,
+                    semaphore: semaphore.clone(),
+                    multi_progress: multi_progress.clone(),
+                    save_metadata: args.metadata,
+                    save_tags: args.tag,
+                    db_path: args.db.clone(),
+                    feed_url: feed.url.clone(),
+                    guid,
+                    podcast_title: feed.title.clone(),
+                    channel_image_url: channel_image_url.clone(),
+                },
+            );
+            downloads.push(download_future);
+        }
+    }
+
+    while let Some(result) = downloads.next().await {
+        if let Err(e) = result {
+            eprintln!("Download error: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs the `update` subcommand: every feed previously archived (and thus recorded in the
+/// database) is re-fetched and only its new episodes are downloaded.
+async fn run_update(args: &Args) -> Result<(), Box<dyn std::error::Error>> {
+    let feeds = {
+        let conn = db::open(&args.db)?;
+        db::list_feeds(&conn)?
+    };
+
+    if feeds.is_empty() {
+        println!("No subscribed feeds recorded yet — archive at least one feed first.");
+        return Ok(());
+    }
+
+    for feed in &feeds {
+        println!("--- Checking {} for new episodes ---", feed.title);
+        if let Err(e) = update_feed(feed, args).await {
+            eprintln!("Failed to update {} ({}): {}", feed.title, feed.url, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs the `search` subcommand: queries the iTunes directory, prints a numbered list of
+/// matches, and optionally prompts the user to archive one on the spot.
+async fn run_search(query: &str, print_only: bool, args: &Args) -> Result<(), Box<dyn std::error::Error>> {
+    let results = itunes::search_podcasts(query).await?;
+
+    if results.is_empty() {
+        println!("No podcasts found for \"{}\"", query);
+        return Ok(());
+    }
+
+    for (index, result) in results.iter().enumerate() {
+        let feed_url = result.feed_url.as_deref().unwrap_or("");
+        match &result.artist_name {
+            Some(artist) => println!("{}. {} — {} ({})", index + 1, result.track_name, artist, feed_url),
+            None => println!("{}. {} ({})", index + 1, result.track_name, feed_url),
+        }
+    }
+
+    if print_only {
+        return Ok(());
+    }
+
+    print!("Pick a podcast to archive (1-{}, or press Enter to skip): ", results.len());
+    std::io::stdout().flush()?;
+
+    let mut choice = String::new();
+    std::io::stdin().read_line(&mut choice)?;
+    let choice = choice.trim();
+    if choice.is_empty() {
+        return Ok(());
+    }
+
+    let Ok(index) = choice.parse::<usize>() else {
+        println!("\"{}\" isn't a number, skipping", choice);
+        return Ok(());
+    };
+    let Some(selected) = results.get(index.wrapping_sub(1)) else {
+        println!("{} is out of range, skipping", index);
+        return Ok(());
+    };
+    let feed_url = selected
+        .feed_url
+        .clone()
+        .ok_or("Selected podcast has no feed URL")?;
+
+    if args.output.is_some() {
+        archive_feed(&feed_url, args).await
+    } else {
+        println!("Feed URL: {}", feed_url);
+        Ok(())
+    }
+}
+
+/// Fills in anything the CLI left unset from the config file. CLI flags always win.
+fn apply_config_defaults(args: &mut Args, config: &config::Config) {
+    if args.output.is_none() {
+        args.output = config.output.clone();
+    }
+    if args.threads.is_none() {
+        args.threads = config.threads;
+    }
+    if args.count.is_none() {
+        args.count = config.count;
+    }
+    if !args.metadata {
+        args.metadata = config.metadata.unwrap_or(false);
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = Args::parse();
+    let config = config::load();
+    apply_config_defaults(&mut args, &config);
+
+    if let Some(Command::Update) = &args.command {
+        return run_update(&args).await;
+    }
+
+    if let Some(Command::Search { query, print_only }) = &args.command {
+        return run_search(query, *print_only, &args).await;
+    }
+
+    if let Some(export_path) = &args.export_opml {
+        let output = args.output.as_deref().ok_or("--output is required with --export-opml")?;
+        let feeds = opml::collect_subscribed_feeds(output)?;
+        opml::write_opml(&feeds, export_path)?;
+        println!("Exported {} feed(s) to {}", feeds.len(), export_path);
+        return Ok(());
+    }
+
+    if let Some(opml_path) = &args.opml {
+        let feeds = opml::parse_opml(opml_path)?;
+        println!("Found {} feed(s) in {}", feeds.len(), opml_path);
+        for feed in &feeds {
+            println!("--- Archiving {} ---", feed.title);
+            if let Err(e) = archive_feed(&feed.xml_url, &args).await {
+                eprintln!("Failed to archive {} ({}): {}", feed.title, feed.xml_url, e);
+            }
+        }
+        return Ok(());
+    }
+
+    if let Some(url) = &args.url {
+        return archive_feed(url, &args).await;
+    }
+
+    if !config.feeds.is_empty() {
+        println!("No --url given; archiving {} feed(s) from config", config.feeds.len());
+        for feed_url in &config.feeds {
+            println!("--- Archiving {} ---", feed_url);
+            if let Err(e) = archive_feed(feed_url, &args).await {
+                eprintln!("Failed to archive {}: {}", feed_url, e);
+            }
+        }
+        return Ok(());
+    }
+
+    Err("Either --url, --opml, or --export-opml must be provided (or add feeds to the config file)".into())
+}
@@ -0,0 +1,133 @@
+use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+
+/// A single subscribed feed, as read from or written to an OPML file.
+#[derive(Debug, Clone)]
+pub struct OpmlFeed {
+    pub title: String,
+    pub xml_url: String,
+}
+
+/// Parses the `<body><outline xmlUrl="..." .../></body>` entries out of an OPML file.
+///
+/// Only the attributes we care about (`text`/`title` and `xmlUrl`) are read; any other
+/// outline attributes or nested structure are ignored.
+pub fn parse_opml(path: &str) -> Result<Vec<OpmlFeed>, Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(path)?;
+    let mut reader = Reader::from_str(&content);
+    reader.trim_text(true);
+
+    let mut feeds = Vec::new();
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Empty(e) | Event::Start(e) if e.name().as_ref() == b"outline" => {
+                let mut xml_url = None;
+                let mut title = None;
+
+                for attr in e.attributes().flatten() {
+                    let value = attr.decode_and_unescape_value(&reader)?.into_owned();
+                    match attr.key.as_ref() {
+                        b"xmlUrl" => xml_url = Some(value),
+                        b"text" if title.is_none() => title = Some(value),
+                        b"title" => title = Some(value),
+                        _ => {}
+                    }
+                }
+
+                if let Some(xml_url) = xml_url {
+                    feeds.push(OpmlFeed {
+                        title: title.unwrap_or_else(|| xml_url.clone()),
+                        xml_url,
+                    });
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(feeds)
+}
+
+/// Writes `feeds` out as a minimal OPML 2.0 document.
+pub fn write_opml(feeds: &[OpmlFeed], path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = Writer::new_with_indent(Cursor::new(Vec::new()), b' ', 2);
+
+    writer.write_event(Event::Decl(quick_xml::events::BytesDecl::new(
+        "1.0", Some("UTF-8"), None,
+    )))?;
+
+    writer.write_event(Event::Start(BytesStart::new("opml").with_attributes(vec![("version", "2.0")])))?;
+
+    writer.write_event(Event::Start(BytesStart::new("head")))?;
+    writer.write_event(Event::Start(BytesStart::new("title")))?;
+    writer.write_event(Event::Text(BytesText::new("Podcast Archiver subscriptions")))?;
+    writer.write_event(Event::End(BytesEnd::new("title")))?;
+    writer.write_event(Event::End(BytesEnd::new("head")))?;
+
+    writer.write_event(Event::Start(BytesStart::new("body")))?;
+    for feed in feeds {
+        let outline = BytesStart::new("outline").with_attributes(vec![
+            ("text", feed.title.as_str()),
+            ("title", feed.title.as_str()),
+            ("type", "rss"),
+            ("xmlUrl", feed.xml_url.as_str()),
+        ]);
+        writer.write_event(Event::Empty(outline))?;
+    }
+    writer.write_event(Event::End(BytesEnd::new("body")))?;
+    writer.write_event(Event::End(BytesEnd::new("opml")))?;
+
+    fs::write(path, writer.into_inner().into_inner())?;
+    Ok(())
+}
+
+/// Walks `output_dir` for subscribed podcast directories and collects them into `OpmlFeed`s.
+///
+/// Each podcast's feed URL is read back from the `.feed_url` marker file that
+/// [`write_feed_marker`] drops in its output directory, so a round trip through
+/// `--opml`/`--export-opml` doesn't depend on re-fetching every feed.
+pub fn collect_subscribed_feeds(output_dir: &str) -> Result<Vec<OpmlFeed>, Box<dyn std::error::Error>> {
+    let mut feeds = Vec::new();
+
+    let entries = match fs::read_dir(output_dir) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(feeds),
+    };
+
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+
+        let marker_path = entry.path().join(".feed_url");
+        if let Ok(xml_url) = fs::read_to_string(&marker_path) {
+            let title = entry
+                .file_name()
+                .to_string_lossy()
+                .into_owned();
+            feeds.push(OpmlFeed {
+                title,
+                xml_url: xml_url.trim().to_string(),
+            });
+        }
+    }
+
+    feeds.sort_by(|a, b| a.title.cmp(&b.title));
+    Ok(feeds)
+}
+
+/// Records the feed URL a podcast directory was archived from, so `--export-opml` can
+/// recover it later without re-parsing RSS.
+pub fn write_feed_marker(podcast_dir: &str, xml_url: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let marker_path = Path::new(podcast_dir).join(".feed_url");
+    fs::write(marker_path, xml_url)?;
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use lofty::config::WriteOptions;
+use lofty::file::{AudioFile, TaggedFileExt};
+use lofty::picture::{MimeType, Picture, PictureType};
+use lofty::probe::Probe;
+use lofty::tag::{Accessor, ItemKey, Tag};
+
+/// Episode metadata to embed as ID3v2/MP4/Vorbis tags, gathered from an RSS item and its
+/// parent channel.
+pub struct EpisodeTags {
+    pub title: String,
+    pub album: String,
+    pub artist: Option<String>,
+    pub pub_date: Option<String>,
+    pub comment: Option<String>,
+    pub artwork_url: Option<String>,
+}
+
+/// Builds the tag set for an episode, falling back to the channel's artwork when the item
+/// itself doesn't expose an `itunes:image`.
+pub fn build_episode_tags(
+    item: &rss::Item,
+    podcast_title: &str,
+    channel_image_url: Option<&str>,
+) -> EpisodeTags {
+    let itunes = item.itunes_ext();
+
+    let artwork_url = itunes
+        .and_then(|ext| ext.image())
+        .map(|s| s.to_string())
+        .or_else(|| channel_image_url.map(|s| s.to_string()));
+
+    let artist = item
+        .author()
+        .map(|s| s.to_string())
+        .or_else(|| itunes.and_then(|ext| ext.author()).map(|s| s.to_string()));
+
+    EpisodeTags {
+        title: item.title().unwrap_or("Unknown Title").to_string(),
+        album: podcast_title.to_string(),
+        artist,
+        pub_date: item.pub_date().map(|s| s.to_string()),
+        comment: item.description().map(|s| s.to_string()),
+        artwork_url,
+    }
+}
+
+/// Opens the downloaded file at `file_path` with `lofty` and writes `tags` into its native
+/// tag format (ID3v2 for mp3, MP4 atoms for m4a, Vorbis comments for ogg, ...).
+pub async fn write_tags(file_path: &str, tags: &EpisodeTags) -> Result<(), Box<dyn std::error::Error>> {
+    let mut tagged_file = Probe::open(file_path)?.read()?;
+
+    if tagged_file.primary_tag().is_none() {
+        let tag_type = tagged_file.primary_tag_type();
+        tagged_file.insert_tag(Tag::new(tag_type));
+    }
+    let tag = tagged_file.primary_tag_mut().expect("tag was just inserted");
+
+    tag.set_title(tags.title.clone());
+    tag.set_album(tags.album.clone());
+    if let Some(artist) = &tags.artist {
+        tag.set_artist(artist.clone());
+    }
+    if let Some(pub_date) = &tags.pub_date {
+        tag.insert_text(ItemKey::RecordingDate, pub_date.clone());
+    }
+    if let Some(comment) = &tags.comment {
+        tag.set_comment(comment.clone());
+    }
+
+    if let Some(artwork_url) = &tags.artwork_url {
+        if let Some(picture) = fetch_artwork(artwork_url).await {
+            tag.push_picture(picture);
+        }
+    }
+
+    tagged_file.save_to_path(file_path, WriteOptions::default())?;
+
+    Ok(())
+}
+
+/// Downloads `artwork_url` and wraps it as a front-cover `Picture`, returning `None` on any
+/// failure so a broken artwork link doesn't prevent the rest of the tags from being written.
+async fn fetch_artwork(artwork_url: &str) -> Option<Picture> {
+    let response = reqwest::get(artwork_url).await.ok()?.error_for_status().ok()?;
+    let bytes = response.bytes().await.ok()?;
+
+    let mime = if artwork_url.ends_with(".png") {
+        MimeType::Png
+    } else {
+        MimeType::Jpeg
+    };
+
+    Some(Picture::new_unchecked(
+        PictureType::CoverFront,
+        Some(mime),
+        None,
+        bytes.to_vec(),
+    ))
+}